@@ -1,23 +1,21 @@
 use bevy::prelude::*;
-use machinery::{AddSimulationExtension, Simulation, SimulationSteps};
-use simulation::CoinSimResults;
+use machinery::{AddSimulationExtension, SimulationSteps};
+use simulation::{CoinFlip, CoinFlipSettings};
 
 fn main() {
     App::build()
         .add_plugins(DefaultPlugins)
         // Number of steps that each simulation will take before the main loop runs again
         .insert_resource(SimulationSteps(10))
-        // Stores the data collected from all of our simulation worlds
-        .init_resource::<Vec<CoinSimResults>>()
         // This "trait extension method" does several things:
-        // 1. Adds each simulation in its own resource
-        // 2. Adds a system to run each simulation world
-        // 3. Adds a system to grab the data from the simulations and collect it
-        .add_simulation(Simulation::<1>::new(0.5, 100))
-        // We can add more copies of our simulation in their own worlds
-        .add_simulation(Simulation::<2>::new(0.1, 100))
+        // 1. The first call for a given `SimulationKind` wires up its
+        //    registry, its stepping system, and its typed results buffer
+        // 2. Every call registers the simulation in the shared registry
+        .add_simulation::<CoinFlip>(CoinFlipSettings { p: 0.5, n_tosses: 100, seed: 42 })
+        // We can add as many simulations as we like, all sharing the same registry
+        .add_simulation::<CoinFlip>(CoinFlipSettings { p: 0.1, n_tosses: 100, seed: 42 })
         // Modifying the parameters as we please
-        .add_simulation(Simulation::<3>::new(1.0, 400))
+        .add_simulation::<CoinFlip>(CoinFlipSettings { p: 1.0, n_tosses: 400, seed: 42 })
         // Systems added to your app will operate on the main world
         // This system is added to PostUpdate, as the simulations themselves are set to run in Update
         .add_system_to_stage(CoreStage::PostUpdate, analysis::report_simulation.system())
@@ -26,63 +24,505 @@ fn main() {
 
 /// Code that is used to set up the multiple-worlds architecture
 mod machinery {
-    use super::simulation::CoinSimResults;
     use bevy::app::AppBuilder;
     use bevy::ecs::schedule::Schedule;
     use bevy::ecs::system::{IntoSystem, Res, ResMut};
     use bevy::ecs::world::World;
+    #[cfg(not(feature = "single-threaded"))]
+    use bevy::tasks::AsyncComputeTaskPool;
+    use rand::distributions::Distribution;
+    use rand::Rng;
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashMap};
+    use std::marker::PhantomData;
 
-    // We can insert many copies of this, which can be operated on in parallel
-    // As long as we choose a new value for N
-    pub struct Simulation<const N: usize> {
+    // Describes a family of simulation worlds: how to build one from its
+    // settings, and how to pull its results back out once it's been stepped.
+    // Implementing this for several types (a coin sim, a random-walk sim,
+    // ...) lets them all share the registry/stepping/collection machinery
+    // below while still collecting into their own typed results buffer.
+    //
+    // Deliberately has no `State` associated type: nothing in `build`,
+    // `extract`, `Simulation`, the registry, or any system ever needed to
+    // name per-world state independently of `Settings`/`Results`, and
+    // `CoinFlip` is the only implementor outside the tests. Add it back if a future
+    // `SimulationKind` (e.g. a random-walk sim) needs to expose intermediate
+    // state that's neither its settings nor its final results.
+    pub trait SimulationKind: Send + Sync + 'static {
+        type Settings;
+        type Results: Clone + Send + Sync;
+
+        fn build(settings: Self::Settings) -> (World, Schedule);
+        fn extract(world: &World) -> Self::Results;
+    }
+
+    // A single, runtime-sized simulation world of kind `K`. Unlike the old
+    // `Simulation<const N: usize>`, any number of these can be registered
+    // without growing the binary with a fresh monomorphization per world.
+    pub struct Simulation<K: SimulationKind> {
         // Stores your data
         pub world: World,
         // Stores your systems
         pub schedule: Schedule,
+        _kind: PhantomData<K>,
+    }
+
+    impl<K: SimulationKind> Simulation<K> {
+        pub fn new(settings: K::Settings) -> Self {
+            let (world, schedule) = K::build(settings);
+            Self {
+                world,
+                schedule,
+                _kind: PhantomData,
+            }
+        }
+    }
+
+    // Holds every simulation of kind `K` that's currently running, so that a
+    // single pair of systems can step and collect all of them, no matter how
+    // many there are.
+    pub struct SimulationRegistry<K: SimulationKind> {
+        pub simulations: Vec<Simulation<K>>,
+    }
+
+    impl<K: SimulationKind> Default for SimulationRegistry<K> {
+        fn default() -> Self {
+            Self {
+                simulations: Vec::new(),
+            }
+        }
     }
 
     pub struct SimulationSteps(pub isize);
 
-    fn run_simulation<const N: usize>(
-        mut simulation: ResMut<Simulation<N>>,
+    // Steps every registered world of kind `K` in parallel by handing one
+    // task per `Simulation` to `AsyncComputeTaskPool`. Each `World` is owned
+    // exclusively by its task, so there's no aliasing to worry about.
+    #[cfg(not(feature = "single-threaded"))]
+    fn run_simulations<K: SimulationKind>(
+        mut registry: ResMut<SimulationRegistry<K>>,
         steps: Res<SimulationSteps>,
+        task_pool: Res<AsyncComputeTaskPool>,
     ) {
-        // Bypass the borrow-checker being dumb about DerefMut
-        let simulation = &mut *simulation;
+        let steps = steps.0;
 
-        // Fetches the appropriate Simulation resource from the main world
-        // Then runs the simulation schedule on the simulation world repeatedly
-        for _ in 0..steps.0 {
-            simulation.schedule.run_once(&mut simulation.world);
+        task_pool.scope(|scope| {
+            for simulation in registry.simulations.iter_mut() {
+                scope.spawn(async move {
+                    for _ in 0..steps {
+                        simulation.schedule.run_once(&mut simulation.world);
+                    }
+                });
+            }
+        });
+    }
+
+    // WASM and single-threaded debug builds don't have a task pool worth
+    // spawning onto, and stepping worlds sequentially keeps runs deterministic.
+    #[cfg(feature = "single-threaded")]
+    fn run_simulations<K: SimulationKind>(
+        mut registry: ResMut<SimulationRegistry<K>>,
+        steps: Res<SimulationSteps>,
+    ) {
+        for simulation in registry.simulations.iter_mut() {
+            for _ in 0..steps.0 {
+                simulation.schedule.run_once(&mut simulation.world);
+            }
         }
     }
 
-    fn collect_data<const N: usize>(
-        mut collected_data: ResMut<Vec<CoinSimResults>>,
-        simulation: Res<Simulation<N>>,
+    fn collect_data<K: SimulationKind>(
+        mut collected_data: ResMut<Vec<K::Results>>,
+        registry: Res<SimulationRegistry<K>>,
     ) {
-        // Grab the data
-        let sim_data = simulation.world.get_resource::<CoinSimResults>().unwrap();
+        for simulation in registry.simulations.iter() {
+            collected_data.push(K::extract(&simulation.world));
+        }
+    }
+
+    // Precomputes a sampling distribution once and reuses it to fill a
+    // pooled buffer with many draws per call, instead of constructing a
+    // fresh distribution (and allocating a fresh `Vec`) on every draw.
+    // Store one of these as a resource in a simulation's world, alongside
+    // whatever RNG resource it already carries, to batch-sample Bernoulli,
+    // Normal, Uniform, or any other `rand` distribution.
+    pub struct BatchSampler<T, D: Distribution<T>> {
+        distribution: D,
+        _sample: PhantomData<T>,
+    }
+
+    impl<T, D: Distribution<T>> BatchSampler<T, D> {
+        pub fn new(distribution: D) -> Self {
+            Self {
+                distribution,
+                _sample: PhantomData,
+            }
+        }
+
+        // Clears `buffer` and refills it with `n` fresh draws
+        pub fn sample_into(&self, rng: &mut impl Rng, buffer: &mut Vec<T>, n: usize) {
+            buffer.clear();
+            buffer.extend((0..n).map(|_| self.distribution.sample(rng)));
+        }
+    }
+
+    // A point in simulated time for event-driven simulations. Wrapping in a
+    // newtype keeps it from being confused with `SimulationSteps`, which
+    // counts ticks rather than timestamps.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+    pub struct SimTime(pub u64);
+
+    // Identifies which handler a queued event should run when it comes due.
+    // `PartialOrd`/`Ord` are only derived so `(SimTime, u64, EventId)` is
+    // itself `Ord` for the heap below; the monotonic sequence number always
+    // breaks ties before an `EventId` comparison would matter.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct EventId(pub usize);
+
+    // Resource carried inside an event-driven simulation's world, tracking
+    // every event that's still waiting to fire
+    pub struct EventQueue {
+        // `Reverse` turns this into a min-heap on `(time, sequence)`, so the
+        // earliest-scheduled event always pops first; the sequence number
+        // breaks ties between events scheduled for the same timestamp so
+        // runs stay deterministic
+        heap: BinaryHeap<Reverse<(SimTime, u64, EventId)>>,
+        pub now: SimTime,
+        next_seq: u64,
+    }
+
+    impl EventQueue {
+        pub fn new() -> Self {
+            Self {
+                heap: BinaryHeap::new(),
+                now: SimTime(0),
+                next_seq: 0,
+            }
+        }
+
+        // Handlers may only schedule events at or after `now`: letting an
+        // event jump backwards in time would break the non-decreasing
+        // ordering the driver relies on
+        pub fn schedule(&mut self, time: SimTime, event: EventId) {
+            assert!(time >= self.now, "cannot schedule an event in the past");
+            self.heap.push(Reverse((time, self.next_seq, event)));
+            self.next_seq += 1;
+        }
+
+        fn peek_time(&self) -> Option<SimTime> {
+            self.heap.peek().map(|Reverse((time, ..))| *time)
+        }
+
+        fn pop(&mut self) -> Option<(SimTime, EventId)> {
+            self.heap
+                .pop()
+                .map(|Reverse((time, _, event))| (time, event))
+        }
+    }
+
+    impl Default for EventQueue {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // Handed to an event handler so it can schedule follow-up events without
+    // reaching back into the world for the `EventQueue` resource itself
+    pub struct ScheduleEvent<'a> {
+        queue: &'a mut EventQueue,
+    }
+
+    impl<'a> ScheduleEvent<'a> {
+        pub fn at(&mut self, time: SimTime, event: EventId) {
+            self.queue.schedule(time, event);
+        }
+    }
+
+    type EventHandler = Box<dyn FnMut(&mut World, SimTime, &mut ScheduleEvent) + Send + Sync>;
+
+    // A simulation world that's stepped by processing discrete, timestamped
+    // events rather than a fixed number of ticks. Useful for things like
+    // packet arrivals or queueing systems, where most of simulated time is
+    // spent idle between sparse events.
+    pub struct EventSimulation {
+        pub world: World,
+        // How far (in simulated time) a single pass of `run_event_simulations`
+        // may advance *this* world before yielding back to the main loop.
+        // Stored per-simulation rather than in one shared resource, since
+        // different event sims can legitimately run at different paces.
+        pub time_budget: SimTime,
+        handlers: HashMap<EventId, EventHandler>,
+    }
+
+    impl EventSimulation {
+        pub fn new(mut world: World, time_budget: SimTime) -> Self {
+            if world.get_resource::<EventQueue>().is_none() {
+                world.insert_resource(EventQueue::default());
+            }
+
+            Self {
+                world,
+                time_budget,
+                handlers: HashMap::new(),
+            }
+        }
+
+        // Registers the closure that should run whenever `event` comes due.
+        // Handlers are plain closures over `&mut World`, not Bevy systems.
+        pub fn on_event(
+            mut self,
+            event: EventId,
+            handler: impl FnMut(&mut World, SimTime, &mut ScheduleEvent) + Send + Sync + 'static,
+        ) -> Self {
+            self.handlers.insert(event, Box::new(handler));
+            self
+        }
+    }
+
+    #[derive(Default)]
+    pub struct EventSimulationRegistry {
+        pub simulations: Vec<EventSimulation>,
+    }
+
+    fn run_event_simulations(mut registry: ResMut<EventSimulationRegistry>) {
+        for simulation in registry.simulations.iter_mut() {
+            // The budget is relative to wherever this world's clock already
+            // is, not an absolute ceiling: each pass is allowed to advance
+            // `now` by up to `time_budget`, then yields back to the main
+            // loop so later events get their turn on a future pass.
+            let pass_deadline = {
+                let queue = simulation.world.get_resource::<EventQueue>().unwrap();
+                SimTime(queue.now.0.saturating_add(simulation.time_budget.0))
+            };
+
+            loop {
+                let queue = simulation.world.get_resource::<EventQueue>().unwrap();
+                let due = matches!(queue.peek_time(), Some(time) if time <= pass_deadline);
+                if !due {
+                    break;
+                }
+
+                let (time, event) = {
+                    let mut queue = simulation.world.get_resource_mut::<EventQueue>().unwrap();
+                    let (time, event) = queue.pop().unwrap();
+                    queue.now = time;
+                    (time, event)
+                };
 
-        collected_data.push(sim_data.clone());
+                if let Some(mut handler) = simulation.handlers.remove(&event) {
+                    // The handler needs `&mut World` and `&mut EventQueue` at
+                    // the same time, so pull the queue out of the world for
+                    // the duration of the call and put it back afterwards
+                    let mut queue = simulation.world.remove_resource::<EventQueue>().unwrap();
+                    let mut schedule_event = ScheduleEvent { queue: &mut queue };
+                    handler(&mut simulation.world, time, &mut schedule_event);
+                    simulation.world.insert_resource(queue);
+                    simulation.handlers.insert(event, handler);
+                }
+            }
+        }
     }
 
     // Dummy trait, used to allow us to add a "trait extension method" to AppBuilder
     pub trait AddSimulationExtension {
         // `steps` controls the number of times the simulation will run
-        // for each pass of the main analysis loop
-        fn add_simulation<const N: usize>(&mut self, sim: Simulation<N>) -> &mut Self;
+        // for each pass of the main analysis loop.
+        //
+        // Registering the first simulation of a given kind `K` also wires up
+        // its `SimulationRegistry<K>`, its stepping system, and a
+        // `Vec<K::Results>` for `collect_data::<K>` to push into
+        fn add_simulation<K: SimulationKind>(&mut self, settings: K::Settings) -> &mut Self;
+
+        // Registers a discrete-event simulation, driven by `run_event_simulations`
+        // instead of a fixed tick count. `sim` carries its own `time_budget`
+        // (set via `EventSimulation::new`), so different event sims can run
+        // at different paces under the same driver
+        fn add_event_simulation(&mut self, sim: EventSimulation) -> &mut Self;
+
+        // Expands the Cartesian product of `a_values` and `b_values` into one
+        // simulation per combination, each built by `to_settings` from its
+        // pair of parameters and a seed derived from `base_seed` so every
+        // world in the sweep draws independent, reproducible randomness.
+        // Returns the number of worlds created. Their shared
+        // `Vec<K::Results>` buffer is set up by `add_simulation` itself.
+        fn add_simulation_sweep<K: SimulationKind, A: Clone, B: Clone>(
+            &mut self,
+            a_values: impl IntoIterator<Item = A>,
+            b_values: impl IntoIterator<Item = B>,
+            base_seed: u64,
+            to_settings: impl Fn(A, B, u64) -> K::Settings,
+        ) -> usize;
     }
 
     impl AddSimulationExtension for AppBuilder {
-        fn add_simulation<const N: usize>(&mut self, sim: Simulation<N>) -> &mut Self {
-            // Add the simulation as a resource in the main world
-            self.insert_resource(sim)
-                // Adds a system that runs our simulation `steps` number of times
-                // to CoreStage::Update in the main world
-                .add_system(run_simulation::<N>.system())
-                // Collects the data from the simulation into the central storage
-                .add_system(collect_data::<N>.system())
+        fn add_simulation<K: SimulationKind>(&mut self, settings: K::Settings) -> &mut Self {
+            // The registry (and its systems) only need to be wired up once
+            // per kind, no matter how many simulations of that kind get added
+            if self.world().get_resource::<SimulationRegistry<K>>().is_none() {
+                self.init_resource::<SimulationRegistry<K>>()
+                    .init_resource::<Vec<K::Results>>()
+                    // Steps every registered simulation of kind `K` `steps`
+                    // number of times in CoreStage::Update in the main world
+                    .add_system(run_simulations::<K>.system())
+                    // Collects the data from every simulation of kind `K`
+                    // into its typed results buffer
+                    .add_system(collect_data::<K>.system());
+            }
+
+            self.world_mut()
+                .get_resource_mut::<SimulationRegistry<K>>()
+                .unwrap()
+                .simulations
+                .push(Simulation::<K>::new(settings));
+
+            self
+        }
+
+        fn add_event_simulation(&mut self, sim: EventSimulation) -> &mut Self {
+            if self
+                .world()
+                .get_resource::<EventSimulationRegistry>()
+                .is_none()
+            {
+                self.init_resource::<EventSimulationRegistry>()
+                    .add_system(run_event_simulations.system());
+            }
+
+            self.world_mut()
+                .get_resource_mut::<EventSimulationRegistry>()
+                .unwrap()
+                .simulations
+                .push(sim);
+
+            self
+        }
+
+        fn add_simulation_sweep<K: SimulationKind, A: Clone, B: Clone>(
+            &mut self,
+            a_values: impl IntoIterator<Item = A>,
+            b_values: impl IntoIterator<Item = B>,
+            base_seed: u64,
+            to_settings: impl Fn(A, B, u64) -> K::Settings,
+        ) -> usize {
+            let a_values: Vec<A> = a_values.into_iter().collect();
+            let b_values: Vec<B> = b_values.into_iter().collect();
+
+            let mut count = 0usize;
+            for a in &a_values {
+                for b in &b_values {
+                    let seed = base_seed.wrapping_add(count as u64);
+                    self.add_simulation::<K>(to_settings(a.clone(), b.clone(), seed));
+                    count += 1;
+                }
+            }
+
+            count
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use rand::distributions::Uniform;
+        use rand::rngs::SmallRng;
+        use rand::SeedableRng;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        fn event(id: usize) -> EventId {
+            EventId(id)
+        }
+
+        #[test]
+        fn sample_into_clears_and_refills_with_n_draws() {
+            // `Uniform` rather than `Bernoulli`, to exercise the "any `rand`
+            // distribution" claim rather than just the one CoinFlip uses
+            let sampler = BatchSampler::new(Uniform::new(0.0, 1.0));
+            let mut rng = SmallRng::seed_from_u64(7);
+            let mut buffer = vec![99.0, 99.0, 99.0];
+
+            sampler.sample_into(&mut rng, &mut buffer, 5);
+
+            assert_eq!(buffer.len(), 5);
+            assert!(buffer.iter().all(|&sample| (0.0..1.0).contains(&sample)));
+        }
+
+        struct NoOpKind;
+
+        impl SimulationKind for NoOpKind {
+            type Settings = ();
+            type Results = ();
+
+            fn build(_settings: Self::Settings) -> (World, Schedule) {
+                (World::new(), Schedule::default())
+            }
+
+            fn extract(_world: &World) -> Self::Results {}
+        }
+
+        #[test]
+        fn sweep_visits_every_combination_with_distinct_seeds() {
+            let seeds = Rc::new(RefCell::new(Vec::new()));
+            let mut app = bevy::app::App::build();
+
+            let a_values = [1, 2, 3];
+            let b_values = ["x", "y"];
+
+            let seeds_for_closure = Rc::clone(&seeds);
+            let count = app.add_simulation_sweep::<NoOpKind, _, _>(
+                a_values.iter().copied(),
+                b_values.iter().copied(),
+                100,
+                move |_a, _b, seed| {
+                    seeds_for_closure.borrow_mut().push(seed);
+                },
+            );
+
+            assert_eq!(count, a_values.len() * b_values.len());
+
+            let seeds = seeds.borrow();
+            assert_eq!(seeds.len(), count);
+
+            let mut distinct = seeds.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            assert_eq!(distinct.len(), seeds.len());
+        }
+
+        #[test]
+        fn events_pop_in_non_decreasing_time_order() {
+            let mut queue = EventQueue::new();
+            queue.schedule(SimTime(5), event(0));
+            queue.schedule(SimTime(1), event(1));
+            queue.schedule(SimTime(3), event(2));
+
+            assert_eq!(queue.pop(), Some((SimTime(1), event(1))));
+            assert_eq!(queue.pop(), Some((SimTime(3), event(2))));
+            assert_eq!(queue.pop(), Some((SimTime(5), event(0))));
+            assert_eq!(queue.pop(), None);
+        }
+
+        #[test]
+        fn ties_break_by_scheduling_order_deterministically() {
+            let mut queue = EventQueue::new();
+            queue.schedule(SimTime(10), event(0));
+            queue.schedule(SimTime(10), event(1));
+            queue.schedule(SimTime(10), event(2));
+
+            // All three events share a timestamp, so the monotonically
+            // increasing sequence number should be the only thing deciding
+            // pop order: first scheduled, first popped
+            assert_eq!(queue.pop(), Some((SimTime(10), event(0))));
+            assert_eq!(queue.pop(), Some((SimTime(10), event(1))));
+            assert_eq!(queue.pop(), Some((SimTime(10), event(2))));
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot schedule an event in the past")]
+        fn scheduling_before_now_panics() {
+            let mut queue = EventQueue::new();
+            queue.now = SimTime(10);
+            queue.schedule(SimTime(9), event(0));
         }
     }
 }
@@ -90,11 +530,9 @@ mod machinery {
 /// Code that is used to define how our individual simulations should work
 // We're using a coin flipping simulation for demo purposes
 mod simulation {
-    use super::machinery::Simulation;
+    use super::machinery::{BatchSampler, SimulationKind};
     use bevy::prelude::*;
-    use rand::{
-        distributions::Bernoulli, distributions::Distribution, rngs::SmallRng, SeedableRng,
-    };
+    use rand::{distributions::Bernoulli, rngs::SmallRng, SeedableRng};
 
     /// The results of our simulation
     #[derive(Clone)]
@@ -106,43 +544,52 @@ mod simulation {
         pub n_heads: isize,
     }
 
-    /// Component that stores coin parameters
-    struct CoinOdds {
-        /// Probability of getting heads
-        p: f64,
-    }
+    // Resource that stores simulation parameters
+    struct NTosses(isize);
+
+    // Pooled buffer that `flip_coins` draws this tick's outcomes into, and
+    // `record_coins` reads straight back out of. Reused every tick instead
+    // of allocated fresh, and written to directly rather than via a
+    // per-entity `CoinState` component.
+    struct CoinResultsBuffer(Vec<bool>);
 
-    // Component that stores coin state
-    #[derive(PartialEq, Eq)]
-    enum CoinState {
-        Heads,
-        Tails,
+    /// The settings needed to build a coin-flipping `Simulation`
+    pub struct CoinFlipSettings {
+        pub p: f64,
+        pub n_tosses: isize,
+        /// Seeds this world's RNG, so sweeps of many `CoinFlip` worlds can
+        /// each draw independent, reproducible randomness
+        pub seed: u64,
     }
 
-    // Resource that stores simulation parameters
-    struct NTosses(isize);
+    /// Marker type identifying the coin-flipping `SimulationKind`
+    pub struct CoinFlip;
+
+    impl SimulationKind for CoinFlip {
+        type Settings = CoinFlipSettings;
+        type Results = CoinSimResults;
+
+        fn build(settings: Self::Settings) -> (World, Schedule) {
+            let CoinFlipSettings { p, n_tosses, seed } = settings;
 
-    impl<const N: usize> Simulation<N> {
-        // By using constructor methods, we can control the settings of our simulations
-        pub fn new(p: f64, n_tosses: isize) -> Self {
             // Asserting that your parameters is within range
             // is just good practice
             assert!(p >= 0.0);
             assert!(p <= 1.0);
+            assert!(n_tosses >= 0);
 
             // You can perform setup on the worlds here
             // Or you could add startup systems to your schedule
             let mut world = World::new();
 
-            // Use spawn_batch for better performance
-            for _ in 0..n_tosses {
-                world.spawn().insert(CoinOdds { p });
-            }
-
             // Storing configuration in resources
             world.insert_resource(NTosses(n_tosses));
             // Cheap source of seeded entropy
-            world.insert_resource(SmallRng::seed_from_u64(42));
+            world.insert_resource(SmallRng::seed_from_u64(seed));
+            // Built once and reused every tick, instead of constructing a
+            // fresh `Bernoulli` per coin per tick
+            world.insert_resource(BatchSampler::new(Bernoulli::new(p).unwrap()));
+            world.insert_resource(CoinResultsBuffer(Vec::with_capacity(n_tosses as usize)));
 
             // Storing data collection in a resource
             world.insert_resource(CoinSimResults {
@@ -160,49 +607,31 @@ mod simulation {
 
             let mut recording_stage = SystemStage::single_threaded();
             recording_stage.add_system(record_coins.system());
-            recording_stage.add_system(reset_coins.system());
 
             // You only need to add new stages when you need to process more commands
             schedule.add_stage("simulation", simulation_stage);
             schedule.add_stage("recording", recording_stage);
 
-            // Return an instance of our Simulation type,
-            // to be used as a resource in the main world
-            Self { world, schedule }
+            (world, schedule)
+        }
+
+        fn extract(world: &World) -> Self::Results {
+            world.get_resource::<CoinSimResults>().unwrap().clone()
         }
     }
 
     fn flip_coins(
-        mut commands: Commands,
-        query: Query<(Entity, &CoinOdds)>,
+        n_tosses: Res<NTosses>,
+        sampler: Res<BatchSampler<bool, Bernoulli>>,
         mut rng: ResMut<SmallRng>,
+        mut buffer: ResMut<CoinResultsBuffer>,
     ) {
-        for (entity, odds) in query.iter() {
-            // Obviously generating random values one at a time like this
-            // is pointlessly slow
-            let distribution = Bernoulli::new(odds.p).unwrap();
-            let was_heads = distribution.sample(&mut *rng);
-            if was_heads {
-                commands.entity(entity).insert(CoinState::Heads);
-            } else {
-                commands.entity(entity).insert(CoinState::Tails);
-            }
-        }
-    }
-
-    fn record_coins(query: Query<&CoinState>, mut coin_sim_results: ResMut<CoinSimResults>) {
-        for coin_state in query.iter() {
-            coin_sim_results.n_tosses += 1;
-            if *coin_state == CoinState::Heads {
-                coin_sim_results.n_heads += 1;
-            }
-        }
+        sampler.sample_into(&mut *rng, &mut buffer.0, n_tosses.0 as usize);
     }
 
-    fn reset_coins(query: Query<Entity, With<CoinState>>, mut commands: Commands) {
-        for entity in query.iter() {
-            commands.entity(entity).remove::<CoinState>();
-        }
+    fn record_coins(buffer: Res<CoinResultsBuffer>, mut coin_sim_results: ResMut<CoinSimResults>) {
+        coin_sim_results.n_tosses += buffer.0.len() as isize;
+        coin_sim_results.n_heads += buffer.0.iter().filter(|&&was_heads| was_heads).count() as isize;
     }
 }
 